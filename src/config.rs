@@ -0,0 +1,68 @@
+/* SPDX-License-Identifier: MIT */
+
+use std::path::PathBuf;
+
+/// Policy for whether a device should be set up under virtualization, checked
+/// against the output of `systemd-detect-virt`.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum Virtualization {
+    /// Refuse to run inside a container; allow everything else (the historical default).
+    #[default]
+    Auto,
+    /// Always run, regardless of virtualization.
+    None,
+    /// Refuse to run inside any container.
+    Container,
+    /// Refuse to run inside any virtual machine.
+    Vm,
+    /// Refuse to run under any of these `systemd-detect-virt` identifiers.
+    Deny(Vec<String>),
+    /// Only run under one of these `systemd-detect-virt` identifiers.
+    Allow(Vec<String>),
+}
+
+/// A single zram device, as configured by the user.
+#[derive(Debug, Clone)]
+pub struct Device {
+    pub name: String,
+    pub disksize: u64,
+
+    /// Where to mount a filesystem backed by this device.
+    ///
+    /// When unset, the device is used as swap (the original behaviour).
+    pub mount_point: Option<PathBuf>,
+    /// Filesystem to create on the device, e.g. "ext4" or "xfs".
+    ///
+    /// Required when `mount_point` is set.
+    pub fs_type: Option<String>,
+    /// Extra `Options=` to set on the generated `.mount` unit.
+    pub mount_options: Option<String>,
+
+    /// `comp_algorithm` to set on the zram device before sizing it, e.g. "zstd".
+    pub compression_algorithm: Option<String>,
+    /// `backing_dev` to attach to the zram device before sizing it, for writeback
+    /// of idle/incompressible pages to a real disk.
+    pub writeback_device: Option<PathBuf>,
+
+    /// Whether to set up this device under virtualization.
+    pub virtualization: Virtualization,
+    /// Only set up this device when total host memory is at or below this
+    /// many megabytes; unset means no limit.
+    pub host_memory_limit_mb: Option<u64>,
+}
+
+impl Device {
+    pub fn new(name: String) -> Device {
+        Device {
+            name,
+            disksize: 0,
+            mount_point: None,
+            fs_type: None,
+            mount_options: None,
+            compression_algorithm: None,
+            writeback_device: None,
+            virtualization: Virtualization::default(),
+            host_memory_limit_mb: None,
+        }
+    }
+}
@@ -1,6 +1,6 @@
 /* SPDX-License-Identifier: MIT */
 
-use crate::config::Device;
+use crate::config::{Device, Virtualization};
 use anyhow::{anyhow, Context, Result};
 use std::borrow::Cow;
 use std::env;
@@ -29,14 +29,83 @@ fn make_symlink(dst: &str, src: &Path) -> Result<()> {
     Ok(())
 }
 
-fn virtualization_container() -> Result<bool> {
-    match Command::new("systemd-detect-virt")
-        .arg("--container")
-        .stdout(Stdio::null())
-        .status()
-    {
-        Ok(status) => Ok(status.success()),
-        Err(e) => Err(anyhow!("systemd-detect-virt call failed: {}", e)),
+fn detect_virtualization() -> Result<String> {
+    let output = Command::new("systemd-detect-virt")
+        .stdout(Stdio::piped())
+        .output()
+        .context("systemd-detect-virt call failed")?;
+    Ok(String::from_utf8(output.stdout)
+        .context("systemd-detect-virt produced non-UTF-8 output")?
+        .trim_end()
+        .to_string())
+}
+
+/// Container backends recognised by `systemd-detect-virt(1)`.
+fn is_container(virt: &str) -> bool {
+    matches!(
+        virt,
+        "systemd-nspawn"
+            | "lxc"
+            | "lxc-libvirt"
+            | "docker"
+            | "podman"
+            | "rkt"
+            | "wsl"
+            | "proot"
+            | "pouch"
+            | "openvz"
+            | "zone"
+    )
+}
+
+/// VM backends recognised by `systemd-detect-virt(1)`.
+fn is_vm(virt: &str) -> bool {
+    matches!(
+        virt,
+        "kvm"
+            | "qemu"
+            | "bochs"
+            | "xen"
+            | "uml"
+            | "vmware"
+            | "oracle"
+            | "microsoft"
+            | "zvm"
+            | "parallels"
+            | "bhyve"
+            | "qnx"
+            | "apple"
+            | "sre"
+            | "powervm"
+    )
+}
+
+/// Total usable host memory, in megabytes, as reported by `MemTotal` in
+/// `/proc/meminfo`.
+fn host_memory_mb(root: &str) -> Result<u64> {
+    let meminfo_path = Path::new(root).join("proc/meminfo");
+    let meminfo = fs::read_to_string(&meminfo_path)
+        .with_context(|| format!("Failed to read {}", meminfo_path.display()))?;
+    let kb = meminfo
+        .lines()
+        .find_map(|line| line.strip_prefix("MemTotal:"))
+        .ok_or_else(|| anyhow!("No MemTotal entry in {}", meminfo_path.display()))?
+        .trim()
+        .trim_end_matches("kB")
+        .trim()
+        .parse::<u64>()
+        .with_context(|| format!("Failed to parse MemTotal in {}", meminfo_path.display()))?;
+    Ok(kb / 1024)
+}
+
+fn virtualization_allowed(policy: &Virtualization, detected: &str) -> bool {
+    match policy {
+        Virtualization::Auto => !is_container(detected),
+        Virtualization::None => true,
+        Virtualization::Container => !is_container(detected),
+        Virtualization::Vm => !is_vm(detected),
+        Virtualization::Deny(denied) => !denied.iter().any(|v| v == detected),
+        Virtualization::Allow(allowed) => allowed.iter().any(|v| v == detected),
     }
 }
 
@@ -45,23 +114,63 @@ pub fn run_generator(
     devices: Vec<Device>,
     output_directory: PathBuf,
 ) -> Result<()> {
+    let cmdline_path = Path::new(&root[..]).join("proc/cmdline");
+    let cmdline = fs::read_to_string(&cmdline_path).unwrap_or_default();
+    let devices = match apply_cmdline_overrides(devices, &cmdline).with_context(|| {
+        format!(
+            "Failed to parse kernel command line from {}",
+            cmdline_path.display()
+        )
+    })? {
+        Some(devices) => devices,
+        None => {
+            println!("Disabled by the kernel command line (systemd.zram=0), exiting.");
+            return Ok(());
+        }
+    };
+
     if devices.is_empty() {
         println!("No devices configured, exiting.");
         return Ok(());
     }
 
-    if virtualization_container()? {
-        println!("Running in a container, exiting.");
-        return Ok(());
-    }
+    let detected_virt = detect_virtualization()?;
+    let host_memory_mb = if devices.iter().any(|d| d.host_memory_limit_mb.is_some()) {
+        Some(host_memory_mb(&root)?)
+    } else {
+        None
+    };
 
     let mut devices_made = false;
+    let mut swap_device_made = false;
     for dev in &devices {
-        devices_made |= handle_device(&output_directory, dev)?;
+        if !virtualization_allowed(&dev.virtualization, &detected_virt) {
+            println!(
+                "Skipping {} due to virtualization policy (detected {:?}).",
+                dev.name, detected_virt
+            );
+            continue;
+        }
+        if let (Some(limit), Some(host_memory_mb)) = (dev.host_memory_limit_mb, host_memory_mb) {
+            if host_memory_mb > limit {
+                println!(
+                    "Skipping {} due to host-memory-limit ({}MB host RAM > {}MB limit).",
+                    dev.name, host_memory_mb, limit
+                );
+                continue;
+            }
+        }
+        match handle_device(&output_directory, dev)? {
+            DeviceSetup::Swap => swap_device_made = true,
+            DeviceSetup::Mount => (),
+        }
+        devices_made = true;
     }
     if devices_made {
-        /* We created some devices, let's make sure the module is loaded and creation service is present */
-        make_service_template(&output_directory)?;
+        /* We created some devices, let's make sure the module is loaded */
+        if swap_device_made {
+            make_service_template(&output_directory)?;
+        }
 
         let modules_load_path = Path::new(&root[..]).join("run/modules-load.d/zram.conf");
         make_parent(&modules_load_path)?;
@@ -76,6 +185,69 @@ pub fn run_generator(
     Ok(())
 }
 
+/// Apply `systemd.zram=` and `zram.<name>.<setting>=` kernel command-line
+/// overrides on top of the devices configured on disk.
+///
+/// Returns `Ok(None)` when `systemd.zram=0` disables generation entirely.
+fn apply_cmdline_overrides(mut devices: Vec<Device>, cmdline: &str) -> Result<Option<Vec<Device>>> {
+    let mut forced = None;
+
+    for arg in cmdline.split_whitespace() {
+        let (key, value) = match arg.split_once('=') {
+            Some((key, value)) => (key, Some(value)),
+            None => (arg, None),
+        };
+
+        if key == "systemd.zram" {
+            match value {
+                Some("0") => forced = Some(false),
+                Some("1") => forced = Some(true),
+                Some(other) => return Err(anyhow!("Invalid value for systemd.zram: {}", other)),
+                None => return Err(anyhow!("systemd.zram requires a value")),
+            }
+            continue;
+        }
+
+        let rest = match key.strip_prefix("zram.") {
+            Some(rest) => rest,
+            None => continue,
+        };
+        let (name, setting) = rest
+            .split_once('.')
+            .ok_or_else(|| anyhow!("Malformed kernel argument: {}", arg))?;
+        let value = value.ok_or_else(|| anyhow!("{} requires a value", key))?;
+
+        let device_index = match devices.iter().position(|d| d.name == name) {
+            Some(index) => index,
+            None => {
+                devices.push(Device::new(name.to_string()));
+                devices.len() - 1
+            }
+        };
+        let device = &mut devices[device_index];
+
+        match setting {
+            "disksize" => {
+                device.disksize = value
+                    .parse()
+                    .with_context(|| format!("Invalid value for {}: {}", key, value))?;
+            }
+            "comp-algorithm" => device.compression_algorithm = Some(value.to_string()),
+            other => return Err(anyhow!("Unknown kernel argument: zram.{}.{}", name, other)),
+        }
+    }
+
+    if forced == Some(false) {
+        return Ok(None);
+    }
+
+    if forced == Some(true) && devices.is_empty() {
+        devices.push(Device::new("zram0".to_string()));
+    }
+
+    Ok(Some(devices))
+}
+
 fn make_service_template(output_directory: &Path) -> Result<()> {
     let service_path = output_directory.join("swap-create@.service");
 
@@ -110,7 +282,178 @@ ExecStart={generator} --setup-device '%i'
     Ok(())
 }
 
-fn handle_device(output_directory: &Path, device: &Device) -> Result<bool> {
+fn escape_mount_unit_name(mount_point: &Path) -> Result<String> {
+    let output = Command::new("systemd-escape")
+        .arg("--suffix=mount")
+        .arg("--path")
+        .arg(mount_point)
+        .output()
+        .context("Failed to run systemd-escape")?;
+    if !output.status.success() {
+        return Err(anyhow!(
+            "systemd-escape failed for {}",
+            mount_point.display()
+        ));
+    }
+    Ok(String::from_utf8(output.stdout)
+        .context("systemd-escape produced non-UTF-8 output")?
+        .trim_end()
+        .to_string())
+}
+
+/// Where `setup_device` writes a zram device's sysfs attributes. Exists so
+/// tests can observe write order without touching real `/sys` paths.
+trait SysfsWriter {
+    fn write(&mut self, device_name: &str, attr: &str, value: &str) -> Result<()>;
+}
+
+struct RealSysfsWriter;
+
+impl SysfsWriter for RealSysfsWriter {
+    fn write(&mut self, device_name: &str, attr: &str, value: &str) -> Result<()> {
+        let path = format!("/sys/block/{}/{}", device_name, attr);
+        fs::write(&path, value)
+            .with_context(|| format!("Failed to write {:?} to {}", value, path))
+    }
+}
+
+/// Set up a zram device's sysfs attributes ahead of marking it usable.
+///
+/// `comp_algorithm` and `backing_dev` must be set before `disksize`, as the
+/// kernel rejects changing them once the device has been sized.
+///
+/// This is what `{generator} --setup-device '%i'` runs in the generated
+/// `swap-create@.service` template.
+pub fn setup_device(device: &Device) -> Result<()> {
+    setup_device_with(&mut RealSysfsWriter, device)
+}
+
+fn setup_device_with(writer: &mut dyn SysfsWriter, device: &Device) -> Result<()> {
+    if let Some(algorithm) = &device.compression_algorithm {
+        writer
+            .write(&device.name, "comp_algorithm", algorithm)
+            .with_context(|| format!("Unsupported compression algorithm: {}", algorithm))?;
+    }
+
+    if let Some(backing_dev) = &device.writeback_device {
+        writer.write(
+            &device.name,
+            "backing_dev",
+            &backing_dev.display().to_string(),
+        )?;
+    }
+
+    writer.write(&device.name, "disksize", &device.disksize.to_string())?;
+
+    Ok(())
+}
+
+/// Which kind of unit `handle_device` wrote out for a given device.
+enum DeviceSetup {
+    Swap,
+    Mount,
+}
+
+fn handle_device(output_directory: &Path, device: &Device) -> Result<DeviceSetup> {
+    match &device.mount_point {
+        Some(mount_point) => {
+            handle_mount_device(output_directory, device, mount_point)?;
+            Ok(DeviceSetup::Mount)
+        }
+        None => {
+            handle_swap_device(output_directory, device)?;
+            Ok(DeviceSetup::Swap)
+        }
+    }
+}
+
+fn handle_mount_device(output_directory: &Path, device: &Device, mount_point: &Path) -> Result<()> {
+    let fs_type = device
+        .fs_type
+        .as_deref()
+        .ok_or_else(|| anyhow!("Device {} has a mount point but no fs_type", device.name))?;
+
+    let mount_unit_name = escape_mount_unit_name(mount_point)?;
+    println!(
+        "Creating {} for /dev/{} mounted at {} ({}MB)",
+        mount_unit_name,
+        device.name,
+        mount_point.display(),
+        device.disksize / 1024 / 1024
+    );
+
+    let setup_unit_name = format!("{}-setup.service", device.name);
+    let setup_path = output_directory.join(&setup_unit_name);
+    let setup_contents = format!(
+        "\
+# Automatically generated by zram-generator
+
+[Unit]
+Description=Create filesystem on /dev/{zram_device} for {mount_point}
+Wants=systemd-modules-load.service
+After=systemd-modules-load.service
+After=dev-{zram_device}.device
+DefaultDependencies=false
+
+[Service]
+Type=oneshot
+RemainAfterExit=yes
+ExecStartPre=-modprobe zram
+ExecStart={generator} --setup-device '{zram_device}'
+ExecStart=mkfs.{fs_type} /dev/{zram_device}
+",
+        zram_device = device.name,
+        mount_point = mount_point.display(),
+        fs_type = fs_type,
+        generator = env::current_exe()
+            .context("Couldn't get path to generator executable")?
+            .display(),
+    );
+    fs::write(&setup_path, setup_contents).with_context(|| {
+        format!(
+            "Failed to write a device setup service into {}",
+            setup_path.display()
+        )
+    })?;
+
+    let options_line = match &device.mount_options {
+        Some(options) => format!("Options={}\n", options),
+        None => String::new(),
+    };
+    let mount_path = output_directory.join(&mount_unit_name);
+    let mount_contents = format!(
+        "\
+# Automatically generated by zram-generator
+
+[Unit]
+Description=Compressed filesystem on /dev/{zram_device}
+Requires={setup_unit}
+After={setup_unit}
+
+[Mount]
+What=/dev/{zram_device}
+Where={mount_point}
+Type={fs_type}
+{options}",
+        zram_device = device.name,
+        setup_unit = setup_unit_name,
+        mount_point = mount_point.display(),
+        fs_type = fs_type,
+        options = options_line,
+    );
+    fs::write(&mount_path, mount_contents).with_context(|| {
+        format!("Failed to write a mount unit into {}", mount_path.display())
+    })?;
+
+    let symlink_path = output_directory
+        .join("local-fs.target.wants")
+        .join(&mount_unit_name);
+    let target_path = format!("../{}", mount_unit_name);
+    make_symlink(&target_path, &symlink_path)?;
+    Ok(())
+}
+
+fn handle_swap_device(output_directory: &Path, device: &Device) -> Result<()> {
     let swap_name = format!("dev-{}.swap", device.name);
     println!(
         "Creating {} for /dev/{} ({}MB)",
@@ -146,5 +489,298 @@ Priority=100
     let symlink_path = output_directory.join("swap.target.wants").join(&swap_name);
     let target_path = format!("../{}", swap_name);
     make_symlink(&target_path, &symlink_path)?;
-    Ok(true)
+    Ok(())
+}
+
+#[cfg(test)]
+mod setup_device_tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct RecordingWriter {
+        calls: Vec<(String, String)>,
+    }
+
+    impl SysfsWriter for RecordingWriter {
+        fn write(&mut self, _device_name: &str, attr: &str, value: &str) -> Result<()> {
+            self.calls.push((attr.to_string(), value.to_string()));
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn writes_comp_algorithm_and_backing_dev_before_disksize() {
+        let mut device = Device::new("zram0".to_string());
+        device.disksize = 1024;
+        device.compression_algorithm = Some("zstd".to_string());
+        device.writeback_device = Some(PathBuf::from("/dev/sdb1"));
+
+        let mut writer = RecordingWriter::default();
+        setup_device_with(&mut writer, &device).unwrap();
+
+        assert_eq!(
+            writer.calls,
+            vec![
+                ("comp_algorithm".to_string(), "zstd".to_string()),
+                ("backing_dev".to_string(), "/dev/sdb1".to_string()),
+                ("disksize".to_string(), "1024".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn skips_unset_optional_attrs() {
+        let mut device = Device::new("zram0".to_string());
+        device.disksize = 2048;
+
+        let mut writer = RecordingWriter::default();
+        setup_device_with(&mut writer, &device).unwrap();
+
+        assert_eq!(
+            writer.calls,
+            vec![("disksize".to_string(), "2048".to_string())]
+        );
+    }
+}
+
+#[cfg(test)]
+mod mount_device_tests {
+    use super::*;
+
+    fn temp_output_dir(tag: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!(
+            "zram-generator-test-{}-{}",
+            tag,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn writes_mount_and_setup_units() {
+        let output_directory = temp_output_dir("mount-units");
+
+        let mut device = Device::new("zram0".to_string());
+        device.disksize = 2 * 1024 * 1024;
+        device.fs_type = Some("ext4".to_string());
+        device.mount_options = Some("noatime".to_string());
+
+        let mount_point = PathBuf::from("/mnt/zram-cache");
+        handle_mount_device(&output_directory, &device, &mount_point).unwrap();
+
+        let mount_unit_name = escape_mount_unit_name(&mount_point).unwrap();
+        let setup_unit_name = "zram0-setup.service";
+
+        let mount_contents = fs::read_to_string(output_directory.join(&mount_unit_name)).unwrap();
+        assert!(mount_contents.contains("What=/dev/zram0"));
+        assert!(mount_contents.contains(&format!("Where={}", mount_point.display())));
+        assert!(mount_contents.contains("Type=ext4"));
+        assert!(mount_contents.contains("Options=noatime"));
+        assert!(mount_contents.contains(&format!("Requires={}", setup_unit_name)));
+        assert!(mount_contents.contains(&format!("After={}", setup_unit_name)));
+
+        let setup_contents =
+            fs::read_to_string(output_directory.join(setup_unit_name)).unwrap();
+        assert!(setup_contents.contains("mkfs.ext4 /dev/zram0"));
+
+        let symlink_path = output_directory
+            .join("local-fs.target.wants")
+            .join(&mount_unit_name);
+        assert!(symlink_path.symlink_metadata().is_ok());
+
+        fs::remove_dir_all(&output_directory).unwrap();
+    }
+
+    #[test]
+    fn omits_options_line_when_unset() {
+        let output_directory = temp_output_dir("mount-no-options");
+
+        let mut device = Device::new("zram0".to_string());
+        device.fs_type = Some("xfs".to_string());
+
+        let mount_point = PathBuf::from("/mnt/zram-cache-2");
+        handle_mount_device(&output_directory, &device, &mount_point).unwrap();
+
+        let mount_unit_name = escape_mount_unit_name(&mount_point).unwrap();
+        let mount_contents = fs::read_to_string(output_directory.join(&mount_unit_name)).unwrap();
+        assert!(!mount_contents.contains("Options="));
+
+        fs::remove_dir_all(&output_directory).unwrap();
+    }
+
+    #[test]
+    fn errors_without_fs_type() {
+        let output_directory = temp_output_dir("mount-missing-fstype");
+        let device = Device::new("zram0".to_string());
+
+        let result = handle_mount_device(
+            &output_directory,
+            &device,
+            Path::new("/mnt/zram-cache-3"),
+        );
+        assert!(result.is_err());
+
+        fs::remove_dir_all(&output_directory).unwrap();
+    }
+}
+
+#[cfg(test)]
+mod cmdline_tests {
+    use super::*;
+
+    #[test]
+    fn empty_cmdline_leaves_devices_untouched() {
+        let devices = vec![Device::new("zram0".to_string())];
+        let result = apply_cmdline_overrides(devices.clone(), "").unwrap().unwrap();
+        assert_eq!(result.len(), devices.len());
+        assert_eq!(result[0].name, "zram0");
+    }
+
+    #[test]
+    fn systemd_zram_0_disables_generation() {
+        let devices = vec![Device::new("zram0".to_string())];
+        let result = apply_cmdline_overrides(devices, "quiet systemd.zram=0 splash").unwrap();
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn systemd_zram_1_creates_default_device_when_none_configured() {
+        let result = apply_cmdline_overrides(vec![], "systemd.zram=1").unwrap().unwrap();
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].name, "zram0");
+    }
+
+    #[test]
+    fn systemd_zram_1_does_not_add_a_device_when_some_are_configured() {
+        let devices = vec![Device::new("zram0".to_string())];
+        let result = apply_cmdline_overrides(devices, "systemd.zram=1").unwrap().unwrap();
+        assert_eq!(result.len(), 1);
+    }
+
+    #[test]
+    fn last_systemd_zram_occurrence_wins_disable_then_enable() {
+        let result = apply_cmdline_overrides(vec![], "systemd.zram=0 systemd.zram=1")
+            .unwrap()
+            .unwrap();
+        assert_eq!(result.len(), 1);
+    }
+
+    #[test]
+    fn last_systemd_zram_occurrence_wins_enable_then_disable() {
+        let devices = vec![Device::new("zram0".to_string())];
+        let result = apply_cmdline_overrides(devices, "systemd.zram=1 systemd.zram=0").unwrap();
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn invalid_systemd_zram_value_is_an_error() {
+        let result = apply_cmdline_overrides(vec![], "systemd.zram=2");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn per_device_disksize_override_mutates_matching_device() {
+        let devices = vec![Device::new("zram0".to_string())];
+        let result = apply_cmdline_overrides(devices, "zram.zram0.disksize=1048576")
+            .unwrap()
+            .unwrap();
+        assert_eq!(result[0].disksize, 1048576);
+    }
+
+    #[test]
+    fn per_device_override_for_unknown_device_creates_it() {
+        let result = apply_cmdline_overrides(vec![], "zram.zram1.comp-algorithm=zstd")
+            .unwrap()
+            .unwrap();
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].name, "zram1");
+        assert_eq!(result[0].compression_algorithm.as_deref(), Some("zstd"));
+    }
+
+    #[test]
+    fn malformed_per_device_key_is_an_error() {
+        let result = apply_cmdline_overrides(vec![], "zram.zram0=1");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn unknown_per_device_setting_is_an_error() {
+        let result = apply_cmdline_overrides(vec![], "zram.zram0.bogus=1");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn invalid_disksize_value_is_an_error() {
+        let result = apply_cmdline_overrides(vec![], "zram.zram0.disksize=not-a-number");
+        assert!(result.is_err());
+    }
+}
+
+#[cfg(test)]
+mod virtualization_tests {
+    use super::*;
+
+    #[test]
+    fn recognises_common_container_backends() {
+        for virt in ["systemd-nspawn", "docker", "podman", "lxc"] {
+            assert!(is_container(virt), "{} should be a container", virt);
+            assert!(!is_vm(virt), "{} should not be a vm", virt);
+        }
+    }
+
+    #[test]
+    fn recognises_common_vm_backends() {
+        for virt in ["kvm", "qemu", "vmware", "xen"] {
+            assert!(is_vm(virt), "{} should be a vm", virt);
+            assert!(!is_container(virt), "{} should not be a container", virt);
+        }
+    }
+
+    #[test]
+    fn bare_metal_is_neither() {
+        assert!(!is_container("none"));
+        assert!(!is_vm("none"));
+    }
+
+    #[test]
+    fn auto_blocks_only_containers() {
+        assert!(!virtualization_allowed(&Virtualization::Auto, "docker"));
+        assert!(virtualization_allowed(&Virtualization::Auto, "kvm"));
+        assert!(virtualization_allowed(&Virtualization::Auto, "none"));
+    }
+
+    #[test]
+    fn none_always_allows() {
+        assert!(virtualization_allowed(&Virtualization::None, "docker"));
+        assert!(virtualization_allowed(&Virtualization::None, "kvm"));
+    }
+
+    #[test]
+    fn container_policy_blocks_only_containers() {
+        assert!(!virtualization_allowed(&Virtualization::Container, "lxc"));
+        assert!(virtualization_allowed(&Virtualization::Container, "kvm"));
+    }
+
+    #[test]
+    fn vm_policy_blocks_only_vms() {
+        assert!(!virtualization_allowed(&Virtualization::Vm, "qemu"));
+        assert!(virtualization_allowed(&Virtualization::Vm, "docker"));
+    }
+
+    #[test]
+    fn deny_blocks_listed_identifiers_only() {
+        let policy = Virtualization::Deny(vec!["docker".to_string()]);
+        assert!(!virtualization_allowed(&policy, "docker"));
+        assert!(virtualization_allowed(&policy, "podman"));
+    }
+
+    #[test]
+    fn allow_permits_listed_identifiers_only() {
+        let policy = Virtualization::Allow(vec!["kvm".to_string()]);
+        assert!(virtualization_allowed(&policy, "kvm"));
+        assert!(!virtualization_allowed(&policy, "qemu"));
+        assert!(!virtualization_allowed(&policy, "none"));
+    }
 }